@@ -1,8 +1,13 @@
-use std::{collections::HashMap, env};
+use std::{
+    collections::HashMap,
+    env,
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
+};
 
 use blue_build_utils::constants::{GITHUB_ACTIONS, GITLAB_CI, IMAGE_VERSION_LABEL};
 use clap::ValueEnum;
-use log::trace;
+use log::{trace, warn};
 use serde::Deserialize;
 use serde_json::Value;
 
@@ -15,6 +20,149 @@ pub(super) trait DetermineDriver<T> {
     fn determine_driver(&mut self) -> T;
 }
 
+/// Env var allowing users to pin the exact `docker` binary to use.
+const BB_DOCKER_PATH: &str = "BB_DOCKER_PATH";
+
+/// Env var allowing users to pin the exact `podman` binary to use.
+const BB_PODMAN_PATH: &str = "BB_PODMAN_PATH";
+
+/// Env var allowing users to pin the exact `buildah` binary to use.
+const BB_BUILDAH_PATH: &str = "BB_BUILDAH_PATH";
+
+/// Env var allowing users to pin the exact `skopeo` binary to use.
+const BB_SKOPEO_PATH: &str = "BB_SKOPEO_PATH";
+
+/// Caches the concrete, canonicalized path chosen for each driver binary
+/// so that whichever code actually spawns `docker`/`podman`/`buildah`/
+/// `skopeo` can honor an explicit override instead of re-resolving (or
+/// silently reverting to) the bare command name.
+static RESOLVED_BINARIES: OnceLock<Mutex<HashMap<String, PathBuf>>> = OnceLock::new();
+
+fn record_resolved_path(name: &str, path: &Path) {
+    RESOLVED_BINARIES
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .insert(name.to_string(), path.to_path_buf());
+}
+
+/// Returns the concrete path most recently resolved for `name` (e.g.
+/// `"docker"`, `"podman"`) by [`resolve_binary_path`]. Drivers should
+/// prefer this over the bare command name when actually invoking the
+/// tool, so that `BB_DOCKER_PATH`/`BB_PODMAN_PATH`/… overrides are
+/// honored at execution time, not just when picking a driver type.
+#[must_use]
+pub fn resolved_binary_path(name: &str) -> Option<PathBuf> {
+    RESOLVED_BINARIES
+        .get()?
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .get(name)
+        .cloned()
+}
+
+/// Well-known install locations for `name` that aren't guaranteed to be on
+/// `PATH`.
+fn known_install_locations(name: &str) -> Vec<PathBuf> {
+    let mut locations = vec![
+        PathBuf::from("/usr/bin").join(name),
+        PathBuf::from("/usr/local/bin").join(name),
+    ];
+
+    if let Some(home) = env::var_os("HOME") {
+        // Rootless Podman (and user-local installs of other engines) often
+        // live under `~/.local/bin` rather than on the system `PATH`.
+        locations.push(PathBuf::from(home).join(".local/bin").join(name));
+    }
+
+    locations
+}
+
+/// Resolves the concrete binary backing a driver.
+///
+/// Resolution order:
+/// 1. The `env_override` env var, if set, is treated as an explicit path.
+///    It is used as-is (canonicalized) if it points at a real file.
+///    If it's set but doesn't point at a file, a `warn!` is logged and
+///    resolution falls through to `PATH`/known install locations — this
+///    helper is invoked unconditionally for every driver type on every
+///    run, so a stale or mistyped override left in a shell profile must
+///    not crash an invocation that was never going to use that engine.
+///    Callers that need to hard-fail on a bad override for the engine the
+///    user actually selected should check [`resolved_binary_path`] (or
+///    re-read the env var) themselves once that engine is chosen.
+/// 2. Every directory in `PATH` is walked looking for `name`.
+/// 3. [`known_install_locations`] is checked for well-known install paths
+///    the tool isn't guaranteed to have put on `PATH`.
+///
+/// The concrete, canonicalized path that was chosen is reported via
+/// `trace!` so `--verbose` runs make it obvious which binary is in play,
+/// and is cached for retrieval via [`resolved_binary_path`].
+fn resolve_binary_path(name: &str, env_override: &str) -> Option<PathBuf> {
+    if let Ok(path) = env::var(env_override) {
+        let path = PathBuf::from(path);
+        if path.is_file() {
+            let resolved = path.canonicalize().unwrap_or(path);
+            trace!("Using {env_override} override for {name}: {}", resolved.display());
+            record_resolved_path(name, &resolved);
+            return Some(resolved);
+        }
+
+        warn!(
+            "{env_override} is set to {} but no such file exists; falling back to PATH",
+            path.display(),
+        );
+    }
+
+    if let Some(path_var) = env::var_os("PATH") {
+        for dir in env::split_paths(&path_var) {
+            let candidate = dir.join(name);
+            if candidate.is_file() {
+                let resolved = candidate.canonicalize().unwrap_or(candidate);
+                trace!("Found {name} on PATH at {}", resolved.display());
+                record_resolved_path(name, &resolved);
+                return Some(resolved);
+            }
+        }
+    }
+
+    known_install_locations(name).into_iter().find_map(|candidate| {
+        if candidate.is_file() {
+            let resolved = candidate.canonicalize().unwrap_or_else(|_| candidate.clone());
+            trace!("Found {name} at known install location {}", resolved.display());
+            record_resolved_path(name, &resolved);
+            Some(resolved)
+        } else {
+            None
+        }
+    })
+}
+
+/// Checks whether `name` is usable, either via an explicit `env_override`
+/// path or by resolving it off `PATH`/known install locations.
+fn command_available(name: &str, env_override: &str) -> bool {
+    resolve_binary_path(name, env_override).is_some()
+        || blue_build_utils::check_command_exists(name).is_ok()
+}
+
+/// `clap` value parser for `--docker-path`/`--podman-path`/`--buildah-path`/
+/// `--skopeo-path`-style CLI overrides: accepts the same "must be an
+/// existing file" contract as the `BB_*_PATH` env vars, so a bad flag is
+/// rejected at arg-parsing time with a normal clap error instead of
+/// surfacing later as a confusing driver-detection failure.
+///
+/// # Errors
+///
+/// Returns an error message if `value` doesn't point at an existing file.
+pub fn parse_binary_path_override(value: &str) -> Result<PathBuf, String> {
+    let path = PathBuf::from(value);
+    if path.is_file() {
+        Ok(path)
+    } else {
+        Err(format!("`{value}` is not an existing file"))
+    }
+}
+
 #[derive(Debug, Clone, Copy, ValueEnum)]
 pub enum InspectDriverType {
     Skopeo,
@@ -24,22 +172,26 @@ pub enum InspectDriverType {
 
 impl DetermineDriver<InspectDriverType> for Option<InspectDriverType> {
     fn determine_driver(&mut self) -> InspectDriverType {
-        *self.get_or_insert(
-            match (
-                blue_build_utils::check_command_exists("skopeo"),
-                blue_build_utils::check_command_exists("docker"),
-                blue_build_utils::check_command_exists("podman"),
-            ) {
-                (Ok(_skopeo), _, _) => InspectDriverType::Skopeo,
-                (_, Ok(_docker), _) => InspectDriverType::Docker,
-                (_, _, Ok(_podman)) => InspectDriverType::Podman,
-                _ => panic!(
-                    "{}{}",
-                    "Could not determine inspection strategy. ",
-                    "You need either skopeo, docker, or podman",
-                ),
-            },
-        )
+        // Checked with short-circuiting `if`/`else` rather than a tuple
+        // match so that lower-priority drivers (e.g. docker) aren't probed
+        // at all, and a stale BB_*_PATH override for them (e.g.
+        // BB_DOCKER_PATH) logs no warning, when a higher-priority one
+        // (skopeo) is already available and would have been chosen anyway.
+        let driver = if command_available("skopeo", BB_SKOPEO_PATH) {
+            InspectDriverType::Skopeo
+        } else if command_available("docker", BB_DOCKER_PATH) {
+            InspectDriverType::Docker
+        } else if command_available("podman", BB_PODMAN_PATH) {
+            InspectDriverType::Podman
+        } else {
+            panic!(
+                "{}{}",
+                "Could not determine inspection strategy. ",
+                "You need either skopeo, docker, or podman",
+            )
+        };
+
+        *self.get_or_insert(driver)
     }
 }
 
@@ -52,33 +204,35 @@ pub enum BuildDriverType {
 
 impl DetermineDriver<BuildDriverType> for Option<BuildDriverType> {
     fn determine_driver(&mut self) -> BuildDriverType {
-        *self.get_or_insert(
-            match (
-                blue_build_utils::check_command_exists("docker"),
-                blue_build_utils::check_command_exists("podman"),
-                blue_build_utils::check_command_exists("buildah"),
-            ) {
-                (Ok(_docker), _, _) if DockerDriver::is_supported_version() => {
-                    BuildDriverType::Docker
-                }
-                (_, Ok(_podman), _) if PodmanDriver::is_supported_version() => {
-                    BuildDriverType::Podman
-                }
-                (_, _, Ok(_buildah)) if BuildahDriver::is_supported_version() => {
-                    BuildDriverType::Buildah
-                }
-                _ => panic!(
-                    "{}{}{}{}",
-                    "Could not determine strategy, ",
-                    format_args!("need either docker version {}, ", DockerDriver::VERSION_REQ,),
-                    format_args!("podman version {}, ", PodmanDriver::VERSION_REQ,),
-                    format_args!(
-                        "or buildah version {} to continue",
-                        BuildahDriver::VERSION_REQ,
-                    ),
+        // Short-circuiting `if`/`else`, same reasoning as
+        // `InspectDriverType`: a lower-priority driver (and its override)
+        // isn't probed once a higher-priority one is already chosen.
+        let driver = if command_available("docker", BB_DOCKER_PATH)
+            && DockerDriver::is_supported_version()
+        {
+            BuildDriverType::Docker
+        } else if command_available("podman", BB_PODMAN_PATH)
+            && PodmanDriver::is_supported_version()
+        {
+            BuildDriverType::Podman
+        } else if command_available("buildah", BB_BUILDAH_PATH)
+            && BuildahDriver::is_supported_version()
+        {
+            BuildDriverType::Buildah
+        } else {
+            panic!(
+                "{}{}{}{}",
+                "Could not determine strategy, ",
+                format_args!("need either docker version {}, ", DockerDriver::VERSION_REQ,),
+                format_args!("podman version {}, ", PodmanDriver::VERSION_REQ,),
+                format_args!(
+                    "or buildah version {} to continue",
+                    BuildahDriver::VERSION_REQ,
                 ),
-            },
-        )
+            )
+        };
+
+        *self.get_or_insert(driver)
     }
 }
 
@@ -127,50 +281,159 @@ impl DetermineDriver<RunDriverType> for Option<RunDriverType> {
     fn determine_driver(&mut self) -> RunDriverType {
         trace!("RunDriver::determine_driver()");
 
-        *self.get_or_insert(
-            match (
-                blue_build_utils::check_command_exists("docker"),
-                blue_build_utils::check_command_exists("podman"),
-            ) {
-                (Ok(_docker), _) if DockerDriver::is_supported_version() => RunDriverType::Docker,
-                (_, Ok(_podman)) if PodmanDriver::is_supported_version() => RunDriverType::Podman,
-                _ => panic!(
-                    "{}{}{}{}",
-                    "Could not determine strategy, ",
-                    format_args!("need either docker version {}, ", DockerDriver::VERSION_REQ),
-                    format_args!("podman version {}, ", PodmanDriver::VERSION_REQ),
-                    format_args!(
-                        "or buildah version {} to continue",
-                        BuildahDriver::VERSION_REQ
-                    ),
+        // Short-circuiting `if`/`else`, same reasoning as
+        // `InspectDriverType`/`BuildDriverType`.
+        let driver = if command_available("docker", BB_DOCKER_PATH)
+            && DockerDriver::is_supported_version()
+        {
+            RunDriverType::Docker
+        } else if command_available("podman", BB_PODMAN_PATH)
+            && PodmanDriver::is_supported_version()
+        {
+            RunDriverType::Podman
+        } else {
+            panic!(
+                "{}{}{}{}",
+                "Could not determine strategy, ",
+                format_args!("need either docker version {}, ", DockerDriver::VERSION_REQ),
+                format_args!("podman version {}, ", PodmanDriver::VERSION_REQ),
+                format_args!(
+                    "or buildah version {} to continue",
+                    BuildahDriver::VERSION_REQ
                 ),
-            },
-        )
+            )
+        };
+
+        *self.get_or_insert(driver)
     }
 }
 
+/// Set to `true` by Drone CI.
+const DRONE: &str = "DRONE";
+
+/// Set to `true` by Cirrus CI.
+const CIRRUS_CI: &str = "CIRRUS_CI";
+
+/// Set by Jenkins to the base URL of the controller; its mere presence
+/// indicates we're running under Jenkins.
+const JENKINS_URL: &str = "JENKINS_URL";
+
 #[derive(Debug, Clone, Copy, ValueEnum)]
 pub enum CiDriverType {
     Local,
     Gitlab,
     Github,
+    Drone,
+    Cirrus,
+    Jenkins,
 }
 
 impl DetermineDriver<CiDriverType> for Option<CiDriverType> {
     fn determine_driver(&mut self) -> CiDriverType {
         trace!("CiDriverType::determine_driver()");
 
-        *self.get_or_insert(
-            match (env::var(GITLAB_CI).ok(), env::var(GITHUB_ACTIONS).ok()) {
-                (Some(_gitlab_ci), None) => CiDriverType::Gitlab,
-                (None, Some(_github_actions)) => CiDriverType::Github,
-                _ => CiDriverType::Local,
-            },
-        )
+        // Checked in a fixed order so that a runner which nests another CI
+        // system's env vars (e.g. a Jenkins agent invoked from within a
+        // GitLab pipeline) still resolves deterministically instead of
+        // depending on map/tuple iteration order.
+        let driver = if env::var(GITLAB_CI).is_ok() {
+            CiDriverType::Gitlab
+        } else if env::var(GITHUB_ACTIONS).is_ok() {
+            CiDriverType::Github
+        } else if env::var(DRONE).is_ok() {
+            CiDriverType::Drone
+        } else if env::var(CIRRUS_CI).is_ok() {
+            CiDriverType::Cirrus
+        } else if env::var(JENKINS_URL).is_ok() {
+            CiDriverType::Jenkins
+        } else {
+            CiDriverType::Local
+        };
+
+        trace!("Determined CI driver: {driver:?}");
+
+        *self.get_or_insert(driver)
+    }
+}
+
+impl CiDriverType {
+    /// Best-effort container registry this CI system publishes images to,
+    /// derived from its own env vars. `None` when the CI system gives us
+    /// nothing to build a registry URL from (e.g. running locally, or a
+    /// CI system with no registry convention of its own).
+    #[must_use]
+    pub fn registry_url(&self) -> Option<String> {
+        match self {
+            Self::Gitlab => env::var("CI_REGISTRY_IMAGE").ok(),
+            Self::Github => env::var("GITHUB_REPOSITORY")
+                .ok()
+                .map(|repo| format!("ghcr.io/{}", repo.to_lowercase())),
+            // Unlike GitHub Actions, neither Drone nor Cirrus has a
+            // built-in relationship with any particular registry — users
+            // push to Docker Hub, Quay, GHCR, or a self-hosted registry
+            // with no convention tying the repo name to one of them, so
+            // guessing `ghcr.io/{repo}` here would just be wrong for
+            // anyone not using GHCR.
+            Self::Drone | Self::Cirrus | Self::Jenkins | Self::Local => None,
+        }
+    }
+
+    /// The git ref (tag, falling back to branch) this CI run is building,
+    /// if the CI system exposes one.
+    #[must_use]
+    pub fn git_ref(&self) -> Option<String> {
+        match self {
+            Self::Gitlab => env::var("CI_COMMIT_TAG")
+                .or_else(|_| env::var("CI_COMMIT_REF_NAME"))
+                .ok(),
+            Self::Github => env::var("GITHUB_REF_NAME").ok(),
+            Self::Drone => env::var("DRONE_TAG")
+                .or_else(|_| env::var("DRONE_COMMIT_BRANCH"))
+                .ok(),
+            Self::Cirrus => env::var("CIRRUS_TAG")
+                .or_else(|_| env::var("CIRRUS_BRANCH"))
+                .ok(),
+            Self::Jenkins => env::var("GIT_BRANCH").ok(),
+            Self::Local => None,
+        }
+    }
+
+    /// An OIDC token for keyless (Sigstore/cosign) signing, if this CI
+    /// system issues one natively. Drone, Cirrus, and Jenkins have no
+    /// built-in OIDC token issuance (it requires an extra plugin/step the
+    /// pipeline would have to set up itself), so they report `None` here
+    /// rather than guessing at a convention that doesn't exist.
+    #[must_use]
+    pub fn oidc_token(&self) -> Option<String> {
+        match self {
+            Self::Gitlab => env::var("CI_JOB_JWT_V2").ok(),
+            Self::Github => env::var("ACTIONS_ID_TOKEN_REQUEST_TOKEN").ok(),
+            Self::Drone | Self::Cirrus | Self::Jenkins | Self::Local => None,
+        }
+    }
+
+    /// The keyless-signing certificate identity cosign would verify
+    /// against, for the CI systems whose OIDC claims follow a documented,
+    /// stable format.
+    #[must_use]
+    pub fn keyless_identity(&self) -> Option<String> {
+        match self {
+            // `GITHUB_WORKFLOW_REF` already starts with `owner/repo/`.
+            Self::Github => Some(format!(
+                "https://github.com/{}",
+                env::var("GITHUB_WORKFLOW_REF").ok()?,
+            )),
+            Self::Gitlab => Some(format!(
+                "{}//.gitlab-ci.yml@{}",
+                env::var("CI_PROJECT_URL").ok()?,
+                env::var("CI_COMMIT_REF_NAME").ok()?,
+            )),
+            Self::Drone | Self::Cirrus | Self::Jenkins | Self::Local => None,
+        }
     }
 }
 
-#[derive(Debug, Default, Clone, Copy, ValueEnum)]
+#[derive(Debug, Default, Clone, Copy, ValueEnum, PartialEq, Eq)]
 pub enum Platform {
     #[default]
     #[value(name = "native")]
@@ -180,31 +443,133 @@ pub enum Platform {
 
     #[value(name = "linux/arm64")]
     LinuxArm64,
+
+    #[value(name = "linux/arm/v7")]
+    LinuxArmV7,
+
+    #[value(name = "linux/386")]
+    LinuxI386,
+
+    #[value(name = "linux/ppc64le")]
+    LinuxPpc64le,
+
+    #[value(name = "linux/s390x")]
+    LinuxS390x,
+
+    #[value(name = "linux/riscv64")]
+    LinuxRiscv64,
 }
 
 impl Platform {
-    /// The architecture of the platform.
+    /// The architecture of the platform, without the `variant` component.
     #[must_use]
     pub const fn arch(&self) -> &str {
         match *self {
             Self::Native => "native",
             Self::LinuxAmd64 => "amd64",
             Self::LinuxArm64 => "arm64",
+            Self::LinuxArmV7 => "arm",
+            Self::LinuxI386 => "386",
+            Self::LinuxPpc64le => "ppc64le",
+            Self::LinuxS390x => "s390x",
+            Self::LinuxRiscv64 => "riscv64",
+        }
+    }
+
+    /// The OCI `variant` component of the platform, if one applies.
+    #[must_use]
+    pub const fn variant(&self) -> Option<&str> {
+        match *self {
+            Self::LinuxArmV7 => Some("v7"),
+            _ => None,
+        }
+    }
+
+    /// The `std::env::consts::ARCH` (Rust target arch) equivalent of this
+    /// platform's OCI arch string, e.g. `"amd64"` -> `"x86_64"`. OCI and
+    /// Rust use different vocabularies for the same architectures, so this
+    /// mapping is required before comparing against the host's arch.
+    #[must_use]
+    pub const fn rust_arch(&self) -> &str {
+        match *self {
+            Self::Native => std::env::consts::ARCH,
+            Self::LinuxAmd64 => "x86_64",
+            Self::LinuxArm64 => "aarch64",
+            Self::LinuxArmV7 => "arm",
+            Self::LinuxI386 => "x86",
+            Self::LinuxPpc64le => "powerpc64",
+            Self::LinuxS390x => "s390x",
+            Self::LinuxRiscv64 => "riscv64",
+        }
+    }
+
+    /// Whether building for this platform requires QEMU/binfmt emulation
+    /// on the current host (i.e. it targets a different architecture than
+    /// the one we're running on).
+    #[must_use]
+    pub fn requires_emulation(&self) -> bool {
+        !matches!(self, Self::Native) && self.rust_arch() != std::env::consts::ARCH
+    }
+
+    /// The name `qemu-user-static`'s `binfmt_misc` registration uses for
+    /// this platform (e.g. `qemu-x86_64`, `qemu-ppc64le`) — its own
+    /// vocabulary, which matches neither the OCI arch string
+    /// ([`Self::arch`]) nor the Rust target arch ([`Self::rust_arch`]) for
+    /// every platform (e.g. `i386` vs. `386` and `x86`).
+    const fn binfmt_handler_arch(&self) -> &str {
+        match *self {
+            Self::Native => "",
+            Self::LinuxAmd64 => "x86_64",
+            Self::LinuxArm64 => "aarch64",
+            Self::LinuxArmV7 => "arm",
+            Self::LinuxI386 => "i386",
+            Self::LinuxPpc64le => "ppc64le",
+            Self::LinuxS390x => "s390x",
+            Self::LinuxRiscv64 => "riscv64",
         }
     }
+
+    /// Verifies that QEMU user-mode emulation is registered with the
+    /// kernel's `binfmt_misc` handler before attempting a cross-arch build
+    /// under Docker/Podman, so a missing `qemu-user-static` setup fails
+    /// fast with an actionable message instead of mid-build.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the missing handler when `binfmt_misc` has
+    /// no registered entry for this platform's architecture.
+    pub fn ensure_emulation_available(&self) -> Result<(), String> {
+        if !self.requires_emulation() {
+            return Ok(());
+        }
+
+        let handler = format!("/proc/sys/fs/binfmt_misc/qemu-{}", self.binfmt_handler_arch());
+        if Path::new(&handler).exists() {
+            trace!("Found binfmt_misc handler for {self} at {handler}");
+            return Ok(());
+        }
+
+        Err(format!(
+            "{}{}{}",
+            format_args!("No binfmt_misc handler registered for {self} ({handler} not found). "),
+            "Cross-building for this platform requires QEMU user-mode emulation. ",
+            "Install the `qemu-user-static` package (or run `docker run --privileged --rm tonistiigi/binfmt --install all`) and try again.",
+        ))
+    }
 }
 
 impl std::fmt::Display for Platform {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match *self {
-                Self::Native => "native",
-                Self::LinuxAmd64 => "linux/amd64",
-                Self::LinuxArm64 => "linux/arm64",
+        match *self {
+            Self::Native => write!(f, "native"),
+            platform => {
+                write!(f, "linux/{}", platform.arch())?;
+                if let Some(variant) = platform.variant() {
+                    write!(f, "/{variant}")?;
+                }
+                Ok(())
             }
-        )
+        }
     }
 }
 
@@ -227,3 +592,427 @@ impl ImageMetadata {
         )
     }
 }
+
+/// Label used to stamp a built image's [`LibcFloor`] audit result, mirroring
+/// how [`IMAGE_VERSION_LABEL`] records the BlueBuild image version.
+pub const LIBC_AUDIT_LABEL: &str = "dev.blue-build.libc-floor";
+
+/// The libc ABI an image's binaries were linked against, as determined by
+/// [`audit_libc_floor`]. Analogous to `auditwheel`'s manylinux/musllinux
+/// classification for Python wheels.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(tag = "libc", rename_all = "lowercase")]
+pub enum LibcFloor {
+    /// Every dynamically-linked ELF binary in the image requires no more
+    /// than this glibc version to run.
+    Glibc {
+        #[serde(rename = "min_version")]
+        min_version: String,
+    },
+    /// The image's binaries are linked against musl rather than glibc.
+    Musl,
+    /// No dynamically-linked binary with a glibc version requirement was
+    /// found (e.g. the image is entirely statically linked, or no ELF
+    /// binaries were under the scanned directories at all). Distinct from
+    /// `Glibc { min_version: "0.0" }`, which would falsely claim the image
+    /// runs on any glibc.
+    Unknown,
+}
+
+impl std::fmt::Display for LibcFloor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Glibc { min_version } => write!(f, "glibc {min_version}"),
+            Self::Musl => write!(f, "musl"),
+            Self::Unknown => write!(f, "unknown (no dynamically-linked glibc binaries found)"),
+        }
+    }
+}
+
+/// Walks every ELF binary under `root`'s `PATH`/lib directories and
+/// determines the minimum glibc version the image's binaries require to
+/// run, or detects musl instead.
+///
+/// Statically-linked binaries contribute no glibc requirement and are
+/// skipped; non-ELF files are ignored. An image whose binaries mix musl
+/// and glibc is almost certainly broken, so that combination is reported
+/// as an error rather than silently picking one. If none of the scanned
+/// directories exist under `root` at all, that almost always means `root`
+/// isn't a valid image rootfs, so it's an error rather than a silent
+/// `Unknown`.
+///
+/// # Errors
+///
+/// Returns an error if `root` can't be walked, if none of the scanned
+/// directories exist under it, or if the image mixes musl and glibc
+/// binaries.
+pub fn audit_libc_floor(root: &Path) -> anyhow::Result<LibcFloor> {
+    let scan_dirs = ["usr/bin", "usr/sbin", "bin", "sbin", "usr/lib", "lib", "lib64"];
+
+    let mut max_glibc: Option<(u64, u64)> = None;
+    let mut found_musl = false;
+    let mut any_dir_scanned = false;
+
+    for dir in scan_dirs {
+        let dir = root.join(dir);
+        if !dir.is_dir() {
+            continue;
+        }
+        any_dir_scanned = true;
+
+        for entry in walkdir::WalkDir::new(dir)
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_file())
+        {
+            let bytes = match std::fs::read(entry.path()) {
+                Ok(bytes) => bytes,
+                Err(err) => {
+                    // An arbitrary, possibly untrusted image rootfs can
+                    // contain permission-denied files, files removed mid-walk,
+                    // or special files that slipped past `is_file()` — one
+                    // unreadable entry shouldn't abort the whole audit.
+                    trace!("Skipping unreadable {}: {err}", entry.path().display());
+                    continue;
+                }
+            };
+            let Ok(elf) = goblin::elf::Elf::parse(&bytes) else {
+                continue; // Not an ELF file, skip.
+            };
+
+            if is_musl(&elf) {
+                found_musl = true;
+                continue;
+            }
+
+            for requirement in glibc_version_requirements(&elf) {
+                max_glibc = Some(max_glibc.map_or(requirement, |current| current.max(requirement)));
+            }
+        }
+    }
+
+    if !any_dir_scanned {
+        return Err(anyhow::anyhow!(
+            "None of {scan_dirs:?} exist under {}; is this a valid image rootfs?",
+            root.display(),
+        ));
+    }
+
+    match (found_musl, max_glibc) {
+        (true, Some(_)) => Err(anyhow::anyhow!(
+            "Image mixes musl and glibc binaries, which indicates a broken build"
+        )),
+        (true, None) => Ok(LibcFloor::Musl),
+        (false, Some((major, minor))) => Ok(LibcFloor::Glibc {
+            min_version: format!("{major}.{minor}"),
+        }),
+        (false, None) => Ok(LibcFloor::Unknown),
+    }
+}
+
+/// An ELF is linked against musl rather than glibc if its `PT_INTERP`
+/// names an `ld-musl-*` loader, or if it `DT_NEEDED`s a bare `libc.so`
+/// (glibc's shared object is always versioned, e.g. `libc.so.6`; musl's
+/// is the unversioned `libc.so`).
+fn is_musl(elf: &goblin::elf::Elf) -> bool {
+    elf.interpreter.is_some_and(|interp| interp.contains("ld-musl-"))
+        || elf.libraries.iter().any(|lib| *lib == "libc.so")
+}
+
+/// Collects every `GLIBC_x.y` symbol version requirement from an ELF's
+/// `VERNEED` section, parsed as `(major, minor)` tuples.
+fn glibc_version_requirements(elf: &goblin::elf::Elf) -> Vec<(u64, u64)> {
+    let Some(verneed) = elf.verneed.as_ref() else {
+        return Vec::new();
+    };
+
+    verneed
+        .iter()
+        .flat_map(|(need, _)| need.iter_aux(&elf.verneed_strtab))
+        .filter_map(|aux| {
+            let name = aux.vna_name(&elf.verneed_strtab).ok()?;
+            name.strip_prefix("GLIBC_")
+                .and_then(|version| version.split_once('.'))
+                .and_then(|(major, minor)| Some((major.parse().ok()?, minor.parse().ok()?)))
+        })
+        .collect()
+}
+
+/// Default minimum free space (in bytes) required on the build driver's
+/// storage root before a build is allowed to start: 10 GiB.
+pub const DEFAULT_MIN_FREE_SPACE: u64 = 10 * 1024 * 1024 * 1024;
+
+/// Default minimum free memory (in bytes) required before a build is
+/// allowed to start: 2 GiB.
+pub const DEFAULT_MIN_FREE_MEMORY: u64 = 2 * 1024 * 1024 * 1024;
+
+/// Disk and memory capacity gathered from the host, backing both the
+/// pre-build preflight check and the `stats` subcommand.
+#[derive(Debug, Clone, Copy, serde::Serialize)]
+pub struct ResourceStats {
+    /// `None` when no mounted disk could be matched to the requested
+    /// storage root (e.g. a relative path, or a sandboxed environment
+    /// where `/proc` mounts aren't visible) — distinct from a real zero.
+    pub free_disk_space: Option<u64>,
+    pub total_disk_space: Option<u64>,
+    pub free_memory: u64,
+    pub total_memory: u64,
+}
+
+impl ResourceStats {
+    /// Gathers disk stats for `storage_root` (the build driver's storage
+    /// path, e.g. `/var/lib/containers`) and system-wide memory stats.
+    #[must_use]
+    pub fn gather(storage_root: &Path) -> Self {
+        let mut sys = sysinfo::System::new();
+        sys.refresh_memory();
+
+        let disks = sysinfo::Disks::new_with_refreshed_list();
+        let disk = disks
+            .iter()
+            .filter(|disk| storage_root.starts_with(disk.mount_point()))
+            .max_by_key(|disk| disk.mount_point().as_os_str().len());
+
+        Self {
+            free_disk_space: disk.map(sysinfo::Disk::available_space),
+            total_disk_space: disk.map(sysinfo::Disk::total_space),
+            free_memory: sys.available_memory(),
+            total_memory: sys.total_memory(),
+        }
+    }
+}
+
+impl std::fmt::Display for ResourceStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.free_disk_space, self.total_disk_space) {
+            (Some(free), Some(total)) => writeln!(
+                f,
+                "Disk:   {} free / {} total",
+                bytesize::ByteSize(free),
+                bytesize::ByteSize(total),
+            )?,
+            _ => writeln!(f, "Disk:   unknown (could not resolve the storage root's mount point)")?,
+        }
+
+        write!(
+            f,
+            "Memory: {} free / {} total",
+            bytesize::ByteSize(self.free_memory),
+            bytesize::ByteSize(self.total_memory),
+        )
+    }
+}
+
+/// `clap` value parser for `--min-free-space`/`--min-free-memory`: accepts
+/// human-readable byte sizes (`10GiB`, `512MB`, …), the same units
+/// `ResourceStats`'s own messages are printed in.
+///
+/// # Errors
+///
+/// Returns an error message if `value` isn't a parseable byte size.
+pub fn parse_byte_threshold(value: &str) -> Result<u64, String> {
+    value
+        .parse::<bytesize::ByteSize>()
+        .map(|size| size.0)
+        .map_err(|e| format!("invalid size `{value}`: {e}"))
+}
+
+/// Whether the resource preflight check should run before a build, per
+/// `--no-preflight`.
+#[derive(Debug, Default, Clone, Copy)]
+pub enum PreflightMode {
+    #[default]
+    Enforce,
+    Skip,
+}
+
+/// Thresholds used by [`ResourceStats::preflight`] to decide whether a
+/// build should be allowed to start. Configurable via `--min-free-space`
+/// and `--min-free-memory`, or skipped entirely with `--no-preflight`.
+#[derive(Debug, Clone, Copy)]
+pub struct PreflightThresholds {
+    pub min_free_space: u64,
+    pub min_free_memory: u64,
+}
+
+impl Default for PreflightThresholds {
+    fn default() -> Self {
+        Self {
+            min_free_space: DEFAULT_MIN_FREE_SPACE,
+            min_free_memory: DEFAULT_MIN_FREE_MEMORY,
+        }
+    }
+}
+
+impl ResourceStats {
+    /// Aborts early with an actionable message if free disk space or
+    /// memory is below `thresholds`, rather than letting a build run until
+    /// it OOMs or fills the container storage path halfway through.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error describing which resource is short and by how
+    /// much.
+    pub fn preflight(storage_root: &Path, thresholds: PreflightThresholds) -> anyhow::Result<()> {
+        let stats = Self::gather(storage_root);
+        trace!("Resource preflight for {}: {stats:?}", storage_root.display());
+
+        match stats.free_disk_space {
+            Some(free) if free < thresholds.min_free_space => {
+                return Err(anyhow::anyhow!(
+                    "Only {} free at {} (need at least {}). Free up space or pass --no-preflight to skip this check.",
+                    bytesize::ByteSize(free),
+                    storage_root.display(),
+                    bytesize::ByteSize(thresholds.min_free_space),
+                ));
+            }
+            Some(_) => {}
+            None => trace!(
+                "Could not determine free disk space for {}, skipping the disk check",
+                storage_root.display(),
+            ),
+        }
+
+        if stats.free_memory < thresholds.min_free_memory {
+            return Err(anyhow::anyhow!(
+                "Only {} of memory free (need at least {}). Free up memory or pass --no-preflight to skip this check.",
+                bytesize::ByteSize(stats.free_memory),
+                bytesize::ByteSize(thresholds.min_free_memory),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Runs [`Self::preflight`] unless `mode` is [`PreflightMode::Skip`],
+    /// matching the `--no-preflight` escape hatch for CI environments that
+    /// manage capacity externally.
+    ///
+    /// # Errors
+    ///
+    /// See [`Self::preflight`].
+    pub fn preflight_if_enabled(
+        storage_root: &Path,
+        thresholds: PreflightThresholds,
+        mode: PreflightMode,
+    ) -> anyhow::Result<()> {
+        match mode {
+            PreflightMode::Skip => {
+                trace!("Skipping resource preflight (--no-preflight)");
+                Ok(())
+            }
+            PreflightMode::Enforce => Self::preflight(storage_root, thresholds),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `cargo test` runs tests in parallel threads within the same
+    /// process, but env vars are process-global — any test that
+    /// sets/removes one needs to hold this for the duration.
+    static ENV_TEST_LOCK: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn resolve_binary_path_falls_through_on_a_nonexistent_override() {
+        let _guard = ENV_TEST_LOCK.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        let env_var = "BB_TEST_NONEXISTENT_BINARY_OVERRIDE";
+        env::set_var(env_var, "/no/such/binary/exists/here");
+        // A bad override must not panic — it should fall through to the
+        // normal PATH/known-install-location search, which will simply
+        // fail to find a binary named "does-not-matter".
+        assert_eq!(resolve_binary_path("does-not-matter", env_var), None);
+        env::remove_var(env_var);
+    }
+
+    #[test]
+    fn parse_binary_path_override_rejects_missing_files() {
+        assert!(parse_binary_path_override("/no/such/binary/exists/here").is_err());
+        let exe = env::current_exe().unwrap();
+        assert!(parse_binary_path_override(exe.to_str().unwrap()).is_ok());
+    }
+
+    #[test]
+    fn platform_arch_and_variant_round_trip_through_display() {
+        assert_eq!(Platform::Native.to_string(), "native");
+        assert_eq!(Platform::LinuxAmd64.to_string(), "linux/amd64");
+        assert_eq!(Platform::LinuxArm64.to_string(), "linux/arm64");
+        assert_eq!(Platform::LinuxArmV7.to_string(), "linux/arm/v7");
+        assert_eq!(Platform::LinuxI386.to_string(), "linux/386");
+        assert_eq!(Platform::LinuxPpc64le.to_string(), "linux/ppc64le");
+        assert_eq!(Platform::LinuxS390x.to_string(), "linux/s390x");
+        assert_eq!(Platform::LinuxRiscv64.to_string(), "linux/riscv64");
+
+        assert_eq!(Platform::LinuxArmV7.variant(), Some("v7"));
+        assert_eq!(Platform::LinuxAmd64.variant(), None);
+    }
+
+    #[test]
+    fn platform_rust_arch_disagrees_with_oci_arch_for_amd64_and_arm64() {
+        // The bug this guards against: naively comparing `arch()` (OCI
+        // vocabulary) against `std::env::consts::ARCH` (Rust vocabulary)
+        // never matches for the two most common targets.
+        assert_ne!(Platform::LinuxAmd64.arch(), Platform::LinuxAmd64.rust_arch());
+        assert_eq!(Platform::LinuxAmd64.rust_arch(), "x86_64");
+        assert_ne!(Platform::LinuxArm64.arch(), Platform::LinuxArm64.rust_arch());
+        assert_eq!(Platform::LinuxArm64.rust_arch(), "aarch64");
+    }
+
+    #[test]
+    fn native_platform_never_requires_emulation() {
+        assert!(!Platform::Native.requires_emulation());
+    }
+
+    #[test]
+    fn libc_floor_display_matches_expected_prose() {
+        assert_eq!(
+            LibcFloor::Glibc { min_version: "2.34".to_string() }.to_string(),
+            "glibc 2.34",
+        );
+        assert_eq!(LibcFloor::Musl.to_string(), "musl");
+        assert_eq!(
+            LibcFloor::Unknown.to_string(),
+            "unknown (no dynamically-linked glibc binaries found)",
+        );
+    }
+
+    #[test]
+    fn audit_libc_floor_errors_on_a_rootfs_missing_every_scan_dir() {
+        let empty_root = std::env::temp_dir().join("bluebuild-types-rs-test-empty-rootfs");
+        let _ = std::fs::remove_dir_all(&empty_root);
+        std::fs::create_dir_all(&empty_root).unwrap();
+
+        let err = audit_libc_floor(&empty_root).unwrap_err();
+        assert!(err.to_string().contains("valid image rootfs"));
+
+        std::fs::remove_dir_all(&empty_root).unwrap();
+    }
+
+    #[test]
+    fn ci_driver_type_prefers_gitlab_over_github_when_both_are_set() {
+        let _guard = ENV_TEST_LOCK.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        env::set_var(GITLAB_CI, "true");
+        env::set_var(GITHUB_ACTIONS, "true");
+
+        let mut driver: Option<CiDriverType> = None;
+        assert!(matches!(driver.determine_driver(), CiDriverType::Gitlab));
+
+        env::remove_var(GITLAB_CI);
+        env::remove_var(GITHUB_ACTIONS);
+    }
+
+    #[test]
+    fn drone_cirrus_jenkins_have_no_native_oidc_token() {
+        assert_eq!(CiDriverType::Drone.oidc_token(), None);
+        assert_eq!(CiDriverType::Cirrus.oidc_token(), None);
+        assert_eq!(CiDriverType::Jenkins.oidc_token(), None);
+    }
+
+    #[test]
+    fn parse_byte_threshold_accepts_human_sizes_and_rejects_garbage() {
+        assert_eq!(parse_byte_threshold("10GiB").unwrap(), 10 * 1024 * 1024 * 1024);
+        assert!(parse_byte_threshold("not-a-size").is_err());
+    }
+}